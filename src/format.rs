@@ -0,0 +1,579 @@
+//! Formatting logic for [`colog`](crate).
+//!
+//! This module provides the [`CologStyle`] trait, which controls how log
+//! records are rendered. Implement it (or override select methods on top of
+//! [`DefaultCologStyle`]) to customize colog's output; see
+//! `examples/custom-level-colors.rs`, `examples/custom-level-tokens.rs` and
+//! `examples/custom-level-prefix.rs` for examples.
+
+use std::io::Error;
+
+use env_logger::fmt::{Color, Formatter, TimestampPrecision};
+use log::{Level, Record};
+
+/// Controls how [`colog`](crate) renders a single log [`Record`].
+///
+/// All methods have default implementations, producing colog's default
+/// style. Override only the methods you need to change; [`format`] is the
+/// single entry point used by [`formatter`](crate::formatter), so most
+/// customizations only need to override the smaller building-block methods
+/// it calls.
+///
+/// [`format`]: CologStyle::format
+pub trait CologStyle {
+    /// Returns the color used for the level prefix of `level`.
+    fn level_color(&self, level: Level) -> Color {
+        match level {
+            Level::Error => Color::Red,
+            Level::Warn => Color::Yellow,
+            Level::Info => Color::Green,
+            Level::Debug => Color::Blue,
+            Level::Trace => Color::Cyan,
+        }
+    }
+
+    /// Returns the text token used to represent `level` (e.g. `"ERROR"`).
+    fn level_token(&self, level: &Level) -> &str {
+        match level {
+            Level::Error => "ERROR",
+            Level::Warn => "WARN",
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        }
+    }
+
+    /// Returns the rendered (unstyled) text of the prefix placed before
+    /// every line of the message, e.g. `" INFO"`.
+    fn prefix_token(&self, level: &Level) -> String {
+        format!("{:>5}", self.level_token(level))
+    }
+
+    /// Returns the timestamp precision to render before the level prefix, or
+    /// `None` (the default) to omit timestamps entirely, matching colog's
+    /// historical behavior.
+    ///
+    /// Override this (or wrap a style in [`TimestampedCologStyle`]) to get
+    /// env_logger-style timestamps (e.g. `2017-11-09T02:12:24Z`), rendered
+    /// dimmed and followed by a space, before the level prefix.
+    fn timestamp(&self) -> Option<TimestampPrecision> {
+        None
+    }
+
+    /// Renders the timestamp segment described by [`timestamp`], styled to
+    /// match the rest of the prefix, or an empty string when no timestamp
+    /// is configured.
+    ///
+    /// [`timestamp`]: CologStyle::timestamp
+    fn format_timestamp(&self, buf: &mut Formatter) -> Result<String, Error> {
+        let Some(precision) = self.timestamp() else {
+            return Ok(String::new());
+        };
+
+        let time = match precision {
+            TimestampPrecision::Seconds => buf.timestamp_seconds().to_string(),
+            TimestampPrecision::Millis => buf.timestamp_millis().to_string(),
+            TimestampPrecision::Micros => buf.timestamp_micros().to_string(),
+            TimestampPrecision::Nanos => buf.timestamp_nanos().to_string(),
+        };
+
+        let styled = buf.style().set_dimmed(true).value(time).to_string();
+
+        Ok(format!("{styled} "))
+    }
+
+    /// Returns whether [`format`](CologStyle::format) should include the
+    /// record's [`target`](Record::target) between the level prefix and the
+    /// message. Defaults to `false`, matching colog's historical behavior.
+    ///
+    /// This is most useful when filtering many crates via `RUST_LOG`, to see
+    /// which module each line came from.
+    fn show_target(&self) -> bool {
+        false
+    }
+
+    /// Renders the target segment, styled to match the rest of the prefix,
+    /// or an empty string when [`show_target`](CologStyle::show_target) is
+    /// `false`.
+    fn format_target(&self, buf: &mut Formatter, record: &Record) -> Result<String, Error> {
+        if !self.show_target() {
+            return Ok(String::new());
+        }
+
+        let styled = buf.style().set_dimmed(true).value(record.target()).to_string();
+
+        Ok(format!("{styled} "))
+    }
+
+    /// Renders `record` into `buf`. This is the single entry point used by
+    /// [`formatter`](crate::formatter) to produce the final output line.
+    ///
+    /// Color is applied through `buf`'s own [`Style`](env_logger::fmt::Style)
+    /// objects, so it's automatically suppressed when the builder's
+    /// [`WriteStyle`](env_logger::WriteStyle) is `Never` (or `Auto` and the
+    /// output isn't a terminal) — see [`basic_builder`](crate::basic_builder)
+    /// for how colog resolves that from `NO_COLOR`/`RUST_LOG_STYLE`.
+    fn format(&self, buf: &mut Formatter, record: &Record) -> Result<(), Error> {
+        use std::io::Write;
+
+        let timestamp = self.format_timestamp(buf)?;
+        let target = self.format_target(buf, record)?;
+
+        let sep = format!("\n{:5} ", "");
+        let mut style = buf.style();
+        style.set_color(self.level_color(record.level())).set_bold(true);
+
+        let prefix = style.value(self.prefix_token(&record.level()));
+        let message = record.args().to_string().replace('\n', &sep);
+
+        writeln!(buf, "{timestamp}{prefix} {target}{message}")
+    }
+}
+
+/// The default [`CologStyle`] implementation, used by
+/// [`basic_builder`](crate::basic_builder) and [`default_builder`](crate::default_builder).
+pub struct DefaultCologStyle;
+
+impl CologStyle for DefaultCologStyle {}
+
+/// Wraps any [`CologStyle`] to additionally render a timestamp, without
+/// requiring a custom trait implementation.
+///
+/// This also wraps styles that override [`format`](CologStyle::format)
+/// entirely, like [`SyslogCologStyle`], by prepending the timestamp and
+/// delegating the rest of the line to `inner.format()` (rather than relying
+/// on the trait's blanket default, which would silently discard `inner`'s
+/// custom rendering).
+///
+/// That said, "wraps any `CologStyle`" only means the timestamp is glued on
+/// as plain text in front of whatever `inner` renders, which is wrong for
+/// styles whose output isn't just human-readable text:
+///
+///  - [`SyslogCologStyle`] needs its `<N>` marker as the very first bytes of
+///    the line for journald to parse it; a timestamp glued in front of that
+///    defeats the point.
+///  - **Don't wrap [`JsonCologStyle`] in this at all** — it emits one JSON
+///    object per line, and gluing a raw, unescaped timestamp string in
+///    front of it produces a line that isn't valid JSON. Use
+///    [`JsonCologStyle::with_timestamp`] instead, which renders the
+///    timestamp as a proper `"timestamp"` field.
+///
+/// ```rust
+/// use colog::format::{CologStyle, DefaultCologStyle, TimestampedCologStyle};
+/// use env_logger::fmt::TimestampPrecision;
+///
+/// let style = TimestampedCologStyle::new(DefaultCologStyle, TimestampPrecision::Seconds);
+/// let mut builder = env_logger::Builder::new();
+/// builder.format(colog::formatter(style));
+/// ```
+///
+/// See also [`default_builder_with_timestamps`](crate::default_builder_with_timestamps)
+/// for a ready-made builder using this wrapper.
+pub struct TimestampedCologStyle<S> {
+    inner: S,
+    precision: TimestampPrecision,
+}
+
+impl<S: CologStyle> TimestampedCologStyle<S> {
+    /// Wraps `inner`, adding a timestamp rendered at `precision`.
+    pub fn new(inner: S, precision: TimestampPrecision) -> Self {
+        Self { inner, precision }
+    }
+}
+
+impl<S: CologStyle> CologStyle for TimestampedCologStyle<S> {
+    fn level_color(&self, level: Level) -> Color {
+        self.inner.level_color(level)
+    }
+
+    fn level_token(&self, level: &Level) -> &str {
+        self.inner.level_token(level)
+    }
+
+    fn prefix_token(&self, level: &Level) -> String {
+        self.inner.prefix_token(level)
+    }
+
+    fn timestamp(&self) -> Option<TimestampPrecision> {
+        Some(self.precision)
+    }
+
+    fn show_target(&self) -> bool {
+        self.inner.show_target()
+    }
+
+    /// Prepends the timestamp, then delegates the rest of the line to
+    /// `inner.format()` — rather than the trait's blanket default — so
+    /// wrapping a style that overrides `format()` itself (like
+    /// [`SyslogCologStyle`]) still renders that style's own output instead
+    /// of silently falling back to plain human-readable text.
+    ///
+    /// See the caveats on [`TimestampedCologStyle`] itself: this puts the
+    /// timestamp *before* whatever `inner` renders, which doesn't fit every
+    /// style (notably [`JsonCologStyle`], which should use
+    /// [`JsonCologStyle::with_timestamp`] instead of this wrapper).
+    fn format(&self, buf: &mut Formatter, record: &Record) -> Result<(), Error> {
+        use std::io::Write;
+
+        let timestamp = self.format_timestamp(buf)?;
+        write!(buf, "{timestamp}")?;
+
+        self.inner.format(buf, record)
+    }
+}
+
+#[cfg(test)]
+mod format_tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    /// A [`std::io::Write`] target shared between an [`env_logger::Builder`]
+    /// and the test, so a formatted [`Record`] can be captured without
+    /// needing a live terminal.
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Renders `record` through `style` via a real [`env_logger::Logger`],
+    /// since [`Formatter`] can't be constructed directly outside `env_logger`
+    /// itself.
+    fn capture(style: impl CologStyle + Sync + Send + 'static, record: &Record) -> String {
+        let shared = SharedBuf::default();
+        let mut builder = env_logger::Builder::new();
+        builder.format(move |buf, rec| style.format(buf, rec));
+        builder.target(env_logger::Target::Pipe(Box::new(shared.clone())));
+        builder.filter_level(log::LevelFilter::Trace);
+        log::Log::log(&builder.build(), record);
+        let bytes = shared.0.lock().unwrap().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn show_target_is_off_by_default() {
+        let record = Record::builder()
+            .level(Level::Info)
+            .target("my::module")
+            .args(format_args!("hello"))
+            .build();
+
+        let out = capture(DefaultCologStyle, &record);
+        assert!(!out.contains("my::module"));
+    }
+
+    struct TargetedStyle;
+
+    impl CologStyle for TargetedStyle {
+        fn show_target(&self) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn show_target_true_includes_the_target() {
+        let record = Record::builder()
+            .level(Level::Info)
+            .target("my::module")
+            .args(format_args!("hello"))
+            .build();
+
+        let out = capture(TargetedStyle, &record);
+        assert!(out.contains("my::module"));
+    }
+
+    #[test]
+    fn wraps_with_timestamp_and_inner_format() {
+        let record = Record::builder()
+            .level(Level::Warn)
+            .target("my::module")
+            .args(format_args!("uh oh"))
+            .build();
+
+        let style = TimestampedCologStyle::new(DefaultCologStyle, TimestampPrecision::Seconds);
+        let out = capture(style, &record);
+
+        // `TimestampedCologStyle::format` prepends a timestamp ahead of
+        // whatever `inner.format()` renders, rather than falling back to the
+        // trait's blanket default.
+        let inner_only = capture(DefaultCologStyle, &record);
+        assert!(out.ends_with(&inner_only));
+        assert!(out.len() > inner_only.len());
+    }
+}
+
+/// A [`CologStyle`] that prefixes each physical line with an sd-daemon /
+/// syslog severity marker (`<N>`) instead of ANSI color, so output piped to
+/// `systemd-cat` or read by journald gets proper severity classification.
+///
+/// journald parses the `<N>` prefix per line and strips it from the stored
+/// message, so multi-line records (which colog already splits across
+/// lines) need the marker repeated on every line, not just the first.
+///
+/// Use [`syslog_builder`](crate::syslog_builder) to get a builder
+/// pre-configured with this style and color disabled.
+pub struct SyslogCologStyle;
+
+impl SyslogCologStyle {
+    /// Returns the syslog priority number for `level`, per the sd-daemon
+    /// convention (`err`=3, `warning`=4, `info`=6, `debug`=7).
+    fn priority(level: Level) -> u8 {
+        match level {
+            Level::Error => 3,
+            Level::Warn => 4,
+            Level::Info => 6,
+            Level::Debug | Level::Trace => 7,
+        }
+    }
+
+    /// Builds the full `<N>`-prefixed line (without a trailing newline) for
+    /// `message`, repeating the marker on every physical line so journald
+    /// can classify each one after stripping it.
+    ///
+    /// Pulled out of [`format`](CologStyle::format) so it can be unit
+    /// tested without a live [`Formatter`].
+    fn render_line(level: Level, prefix: &str, message: &str) -> String {
+        let marker = format!("<{}>", Self::priority(level));
+        let sep = format!("\n{marker}");
+        let body = message.replace('\n', &sep);
+
+        format!("{marker}{prefix} {body}")
+    }
+}
+
+impl CologStyle for SyslogCologStyle {
+    fn format(&self, buf: &mut Formatter, record: &Record) -> Result<(), Error> {
+        use std::io::Write;
+
+        let prefix = self.prefix_token(&record.level());
+        let line = Self::render_line(record.level(), &prefix, &record.args().to_string());
+
+        writeln!(buf, "{line}")
+    }
+}
+
+#[cfg(test)]
+mod syslog_tests {
+    use super::*;
+
+    #[test]
+    fn maps_levels_to_sd_daemon_priorities() {
+        assert_eq!(SyslogCologStyle::priority(Level::Error), 3);
+        assert_eq!(SyslogCologStyle::priority(Level::Warn), 4);
+        assert_eq!(SyslogCologStyle::priority(Level::Info), 6);
+        assert_eq!(SyslogCologStyle::priority(Level::Debug), 7);
+        assert_eq!(SyslogCologStyle::priority(Level::Trace), 7);
+    }
+
+    #[test]
+    fn repeats_the_marker_on_every_physical_line() {
+        let line = SyslogCologStyle::render_line(Level::Error, " ERROR", "first\nsecond\nthird");
+        assert_eq!(line, "<3> ERROR first\n<3>second\n<3>third");
+    }
+
+    #[test]
+    fn single_line_message_gets_one_marker() {
+        let line = SyslogCologStyle::render_line(Level::Info, " INFO", "listening on :8080");
+        assert_eq!(line, "<6> INFO listening on :8080");
+    }
+}
+
+/// A [`CologStyle`] that serializes each [`Record`] as a single-line JSON
+/// object, for feeding colog's output to log shippers instead of a
+/// terminal.
+///
+/// Mirrors the dev/production split env_logger users typically build by
+/// hand: human-colored text locally, structured JSON in production. Use
+/// [`json_builder`](crate::json_builder) to get a builder pre-configured
+/// with this style.
+///
+/// Each line has the shape:
+///
+/// ```json
+/// {"level":"INFO","target":"main","message":"listening on :8080","file":"src/main.rs","line":12}
+/// ```
+///
+/// with `"timestamp"` also present when constructed via
+/// [`with_timestamp`](JsonCologStyle::with_timestamp) (it's omitted by
+/// default, like the rest of colog).
+///
+/// Timestamps are configured directly on this style rather than through
+/// [`TimestampedCologStyle`]: wrapping `JsonCologStyle` in
+/// `TimestampedCologStyle` would glue a raw, unescaped timestamp string in
+/// front of the JSON object, producing a line that isn't valid JSON at all.
+pub struct JsonCologStyle {
+    timestamp: Option<TimestampPrecision>,
+}
+
+impl JsonCologStyle {
+    /// Creates a `JsonCologStyle` with no `"timestamp"` field, matching
+    /// colog's historical behavior.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { timestamp: None }
+    }
+
+    /// Creates a `JsonCologStyle` that includes a `"timestamp"` field,
+    /// rendered at `precision`.
+    #[must_use]
+    pub const fn with_timestamp(precision: TimestampPrecision) -> Self {
+        Self {
+            timestamp: Some(precision),
+        }
+    }
+}
+
+impl Default for JsonCologStyle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JsonCologStyle {
+    /// Builds the single-line JSON object (without a trailing newline) for
+    /// the given fields.
+    ///
+    /// Pulled out of [`format`](CologStyle::format) so it can be unit
+    /// tested without a live [`Formatter`].
+    fn render_line(
+        timestamp: Option<&str>,
+        level: Level,
+        target: &str,
+        message: &str,
+        file: Option<&str>,
+        line: Option<u32>,
+    ) -> String {
+        let mut fields = Vec::new();
+
+        if let Some(timestamp) = timestamp {
+            fields.push(format!(r#""timestamp":"{}""#, escape_json(timestamp)));
+        }
+
+        fields.push(format!(r#""level":"{level}""#));
+        fields.push(format!(r#""target":"{}""#, escape_json(target)));
+        fields.push(format!(r#""message":"{}""#, escape_json(message)));
+
+        if let Some(file) = file {
+            fields.push(format!(r#""file":"{}""#, escape_json(file)));
+        }
+        if let Some(line) = line {
+            fields.push(format!(r#""line":{line}"#));
+        }
+
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+impl CologStyle for JsonCologStyle {
+    fn timestamp(&self) -> Option<TimestampPrecision> {
+        self.timestamp
+    }
+
+    fn format(&self, buf: &mut Formatter, record: &Record) -> Result<(), Error> {
+        use std::io::Write;
+
+        let timestamp = self.timestamp().map(|precision| match precision {
+            TimestampPrecision::Seconds => buf.timestamp_seconds().to_string(),
+            TimestampPrecision::Millis => buf.timestamp_millis().to_string(),
+            TimestampPrecision::Micros => buf.timestamp_micros().to_string(),
+            TimestampPrecision::Nanos => buf.timestamp_nanos().to_string(),
+        });
+
+        let line = Self::render_line(
+            timestamp.as_deref(),
+            record.level(),
+            record.target(),
+            &record.args().to_string(),
+            record.file(),
+            record.line(),
+        );
+
+        writeln!(buf, "{line}")
+    }
+}
+
+/// Escapes `s` for embedding as a JSON string body (without the surrounding
+/// quotes), per <https://www.json.org/>.
+fn escape_json(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod json_tests {
+    use super::*;
+
+    #[test]
+    fn escapes_quotes_backslashes_and_control_chars() {
+        assert_eq!(
+            escape_json("say \"hi\"\\bye\nline2\ttabbed\x01ctrl"),
+            r#"say \"hi\"\\bye\nline2\ttabbed\u0001ctrl"#
+        );
+    }
+
+    #[test]
+    fn leaves_plain_text_untouched() {
+        assert_eq!(escape_json("listening on :8080"), "listening on :8080");
+    }
+
+    #[test]
+    fn renders_required_fields_without_timestamp_or_location() {
+        let line = JsonCologStyle::render_line(None, Level::Info, "main", "hello", None, None);
+        assert_eq!(
+            line,
+            r#"{"level":"INFO","target":"main","message":"hello"}"#
+        );
+    }
+
+    #[test]
+    fn renders_optional_fields_when_present() {
+        let line = JsonCologStyle::render_line(
+            Some("2017-11-09T02:12:24Z"),
+            Level::Error,
+            "main",
+            "boom",
+            Some("src/main.rs"),
+            Some(12),
+        );
+        assert_eq!(
+            line,
+            r#"{"timestamp":"2017-11-09T02:12:24Z","level":"ERROR","target":"main","message":"boom","file":"src/main.rs","line":12}"#
+        );
+    }
+
+    #[test]
+    fn escapes_embedded_quotes_and_newlines_in_the_message() {
+        let line = JsonCologStyle::render_line(
+            None,
+            Level::Warn,
+            "main",
+            "bad input: \"quoted\"\nsecond line",
+            None,
+            None,
+        );
+        assert_eq!(
+            line,
+            r#"{"level":"WARN","target":"main","message":"bad input: \"quoted\"\nsecond line"}"#
+        );
+    }
+}
@@ -51,16 +51,52 @@
 use std::env;
 use std::io::Error;
 
-use env_logger::{fmt::Formatter, Builder};
+use env_logger::{fmt::Formatter, Builder, Env, WriteStyle};
 use log::{LevelFilter, Record};
 
 pub mod format;
 
 use format::CologStyle;
 
+/// Determines the [`WriteStyle`] colog should use, honoring `NO_COLOR`
+/// (<https://no-color.org/>) and env_logger's own `RUST_LOG_STYLE`
+/// (`always`/`auto`/`never`).
+///
+/// `NO_COLOR` takes precedence when both are set, since it's an explicit
+/// opt-out signal. Anything else (including `RUST_LOG_STYLE` being unset or
+/// unrecognized) falls back to `auto`, which only colors a terminal.
+fn write_style_from_env() -> WriteStyle {
+    if env::var_os("NO_COLOR").is_some() {
+        return WriteStyle::Never;
+    }
+
+    match env::var("RUST_LOG_STYLE").as_deref() {
+        Ok("always") => WriteStyle::Always,
+        Ok("never") => WriteStyle::Never,
+        _ => WriteStyle::Auto,
+    }
+}
+
+/// Applies colog's opinionated default level filter
+/// ([`LevelFilter::Info`]), then layers `RUST_LOG` on top if it's set.
+///
+/// Shared by the builder functions that read their filter from `RUST_LOG`
+/// ([`builder_from_env`] reads its filter from its `Env` instead).
+fn apply_info_filter(builder: &mut Builder) {
+    builder.filter(None, LevelFilter::Info);
+    if let Ok(rust_log) = env::var("RUST_LOG") {
+        builder.parse_filters(&rust_log);
+    }
+}
+
 /// Returns a [`env_logger::Builder`] that is configured to use [`crate`]
 /// formatting for its output.
 ///
+/// Color is controlled the same way env_logger does it: `auto` by default
+/// (color only on a terminal), overridable with `RUST_LOG_STYLE` and
+/// `NO_COLOR`. The manual setup shown below leaves out this step, so it
+/// keeps env_logger's own default write style.
+///
 /// This can be used as a building block to integrate into existing
 /// [`env_logger`] applications.
 ///
@@ -78,6 +114,7 @@ use format::CologStyle;
 pub fn basic_builder() -> Builder {
     let mut builder = Builder::new();
     builder.format(formatter(format::DefaultCologStyle));
+    builder.write_style(write_style_from_env());
     builder
 }
 
@@ -96,11 +133,103 @@ pub fn basic_builder() -> Builder {
 /// ```
 #[must_use]
 pub fn default_builder() -> Builder {
+    let mut builder = basic_builder();
+    apply_info_filter(&mut builder);
+    builder
+}
+
+/// Like [`default_builder`], but also renders a timestamp prefix at the
+/// given `precision`, using [`format::TimestampedCologStyle`].
+///
+/// This is a convenient way to approximate env_logger's default output
+/// (which always includes a timestamp) while keeping colog's styling.
+///
+/// ```rust
+/// use env_logger::fmt::TimestampPrecision;
+///
+/// let mut builder = colog::default_builder_with_timestamps(TimestampPrecision::Seconds);
+/// builder.init();
+/// log::info!("logging is ready, with a timestamp");
+/// ```
+#[must_use]
+pub fn default_builder_with_timestamps(precision: env_logger::fmt::TimestampPrecision) -> Builder {
+    let mut builder = Builder::new();
+    builder.format(formatter(format::TimestampedCologStyle::new(
+        format::DefaultCologStyle,
+        precision,
+    )));
+    builder.write_style(write_style_from_env());
+    apply_info_filter(&mut builder);
+    builder
+}
+
+/// Like [`default_builder`], but sources its filter from `env` instead of
+/// the hardcoded `RUST_LOG` variable.
+///
+/// The write style starts from [`basic_builder`]'s `NO_COLOR` /
+/// `RUST_LOG_STYLE` decision; when `env`'s write-style variable is set, its
+/// value replaces that decision.
+///
+/// This lets applications that namespace their logging config (e.g.
+/// `MYAPP_LOG` / `MYAPP_LOG_STYLE`) still get colog's styling and
+/// [`LevelFilter::Info`] default.
+///
+/// ```rust
+/// let env = env_logger::Env::new()
+///     .filter("MYAPP_LOG")
+///     .write_style("MYAPP_LOG_STYLE");
+/// let mut builder = colog::builder_from_env(env);
+/// builder.init();
+/// log::info!("logging is ready");
+/// ```
+#[must_use]
+pub fn builder_from_env(env: Env) -> Builder {
     let mut builder = basic_builder();
     builder.filter(None, LevelFilter::Info);
-    if let Ok(rust_log) = env::var("RUST_LOG") {
-        builder.parse_filters(&rust_log);
-    }
+    builder.parse_env(env);
+    builder
+}
+
+/// Builder pre-configured for syslog/journald-friendly output.
+///
+/// Uses [`format::SyslogCologStyle`], which replaces colog's usual ANSI
+/// color with an sd-daemon `<N>` severity prefix on every line, and
+/// disables color so the prefix isn't decorated. Like [`default_builder`],
+/// it presents messages at [`LevelFilter::Info`] and up by default, and
+/// honors `RUST_LOG` if set.
+///
+/// ```rust
+/// let mut builder = colog::syslog_builder();
+/// builder.init();
+/// log::error!("this becomes a journald-classified `err` line");
+/// ```
+#[must_use]
+pub fn syslog_builder() -> Builder {
+    let mut builder = Builder::new();
+    builder.format(formatter(format::SyslogCologStyle));
+    builder.write_style(WriteStyle::Never);
+    apply_info_filter(&mut builder);
+    builder
+}
+
+/// Builder pre-configured for structured JSON output.
+///
+/// Uses [`format::JsonCologStyle`] to emit one JSON object per log line
+/// instead of colored human text, suitable for log shippers. Like
+/// [`default_builder`], it presents messages at [`LevelFilter::Info`] and
+/// up by default, and honors `RUST_LOG` if set.
+///
+/// ```rust
+/// let mut builder = colog::json_builder();
+/// builder.init();
+/// log::info!("this becomes a JSON line");
+/// ```
+#[must_use]
+pub fn json_builder() -> Builder {
+    let mut builder = Builder::new();
+    builder.format(formatter(format::JsonCologStyle::new()));
+    builder.write_style(WriteStyle::Never);
+    apply_info_filter(&mut builder);
     builder
 }
 
@@ -152,3 +281,63 @@ pub fn formatter(
 ) -> impl Fn(&mut Formatter, &Record<'_>) -> Result<(), Error> + Sync + Send {
     move |buf, rec| fmt.format(buf, rec)
 }
+
+#[cfg(test)]
+mod write_style_tests {
+    use super::*;
+
+    /// Saves `NO_COLOR`/`RUST_LOG_STYLE` on creation and restores them on
+    /// drop, so a single test can freely mutate both without leaking state
+    /// into other tests running in the same process.
+    struct EnvGuard {
+        no_color: Option<String>,
+        rust_log_style: Option<String>,
+    }
+
+    impl EnvGuard {
+        fn new() -> Self {
+            Self {
+                no_color: env::var("NO_COLOR").ok(),
+                rust_log_style: env::var("RUST_LOG_STYLE").ok(),
+            }
+        }
+    }
+
+    impl Drop for EnvGuard {
+        fn drop(&mut self) {
+            match &self.no_color {
+                Some(value) => env::set_var("NO_COLOR", value),
+                None => env::remove_var("NO_COLOR"),
+            }
+            match &self.rust_log_style {
+                Some(value) => env::set_var("RUST_LOG_STYLE", value),
+                None => env::remove_var("RUST_LOG_STYLE"),
+            }
+        }
+    }
+
+    // A single test, rather than one per case: env vars are process-global,
+    // and cargo runs tests in the same process, so separate tests mutating
+    // NO_COLOR/RUST_LOG_STYLE concurrently would be flaky.
+    #[test]
+    fn honors_no_color_and_rust_log_style() {
+        let _guard = EnvGuard::new();
+
+        env::remove_var("NO_COLOR");
+        env::remove_var("RUST_LOG_STYLE");
+        assert_eq!(write_style_from_env(), WriteStyle::Auto);
+
+        env::set_var("RUST_LOG_STYLE", "always");
+        assert_eq!(write_style_from_env(), WriteStyle::Always);
+
+        env::set_var("RUST_LOG_STYLE", "never");
+        assert_eq!(write_style_from_env(), WriteStyle::Never);
+
+        env::set_var("RUST_LOG_STYLE", "garbage");
+        assert_eq!(write_style_from_env(), WriteStyle::Auto);
+
+        env::set_var("NO_COLOR", "1");
+        env::set_var("RUST_LOG_STYLE", "always");
+        assert_eq!(write_style_from_env(), WriteStyle::Never);
+    }
+}